@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::api::networking::v1::NetworkPolicy;
+use k8s_openapi::api::rbac::v1::RoleBinding;
+use kube::core::admission::{AdmissionRequest, AdmissionResponse, AdmissionReview};
+use kube::core::DynamicObject;
+use serde::de::DeserializeOwned;
+use warp::{Filter, Reply};
+
+use crate::checks::{
+    analyze_network_policies, analyze_outdated_components, analyze_pod_insecure_workloads, analyze_role_binding,
+    DEFAULT_TRUSTED_REGISTRIES,
+};
+use crate::finding::{Finding, Severity};
+
+/// Findings at or above `deny_threshold` cause the webhook to deny the
+/// request, and only those blocking findings are reported in the denial.
+/// When nothing blocks, every finding is surfaced as a non-blocking
+/// warning via the response's `warnings` field. `check_overrides` lets operators
+/// override that behavior per check: `Some(severity)` replaces the
+/// threshold for that one check, and `None` means the check never blocks
+/// admission and only ever contributes warnings.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionConfig {
+    pub deny_threshold: Severity,
+    pub check_overrides: HashMap<String, Option<Severity>>,
+}
+
+impl AdmissionConfig {
+    /// Whether `finding` should cause the webhook to deny the request,
+    /// taking any per-check override into account.
+    fn blocks(&self, finding: &Finding) -> bool {
+        match self.check_overrides.get(&finding.check_id) {
+            Some(None) => false,
+            Some(Some(threshold)) => finding.severity >= *threshold,
+            None => finding.severity >= self.deny_threshold,
+        }
+    }
+}
+
+/// Serves the `ValidatingWebhookConfiguration` HTTPS endpoint at
+/// `POST /validate`. `cert_path`/`key_path` are a PEM certificate and key
+/// pair, typically mounted from a Secret by cert-manager or a sidecar.
+pub async fn serve(addr: SocketAddr, cert_path: &str, key_path: &str, config: AdmissionConfig) {
+    let config = Arc::new(config);
+    let route = warp::path("validate")
+        .and(warp::post())
+        .and(warp::body::bytes())
+        .and(warp::any().map(move || config.clone()))
+        .and_then(handle_review);
+
+    warp::serve(route).tls().cert_path(cert_path).key_path(key_path).run(addr).await;
+}
+
+async fn handle_review(body: bytes::Bytes, config: Arc<AdmissionConfig>) -> Result<impl Reply, Infallible> {
+    let review: AdmissionReview<DynamicObject> = match serde_json::from_slice(&body) {
+        Ok(review) => review,
+        Err(e) => return Ok(warp::reply::json(&AdmissionResponse::invalid(e.to_string()).into_review())),
+    };
+    let request: AdmissionRequest<DynamicObject> = match review.try_into() {
+        Ok(request) => request,
+        Err(e) => return Ok(warp::reply::json(&AdmissionResponse::invalid(format!("{:?}", e)).into_review())),
+    };
+
+    Ok(warp::reply::json(&evaluate(&request, &config).into_review()))
+}
+
+/// Runs the same analyzer functions the monitor uses against the object
+/// under review, then turns the findings into an allow/deny response.
+fn evaluate(request: &AdmissionRequest<DynamicObject>, config: &AdmissionConfig) -> AdmissionResponse {
+    let base = AdmissionResponse::from(request);
+    let findings = findings_for(&request.kind.kind, request.object.clone());
+    respond(base, &findings, config)
+}
+
+/// Runs the analyzer function matching `kind` against `object`, if hk8s
+/// has a check for that kind and the object decodes cleanly. Returns no
+/// findings for kinds hk8s doesn't check or objects that fail to decode.
+pub fn findings_for(kind: &str, object: Option<DynamicObject>) -> Vec<Finding> {
+    match kind {
+        "Pod" => object.and_then(decode::<Pod>).map(|pod| {
+            let mut findings = analyze_pod_insecure_workloads(&pod);
+            findings.extend(analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES));
+            findings
+        }),
+        "RoleBinding" => object.and_then(decode::<RoleBinding>).map(|rb| analyze_role_binding(&rb)),
+        "NetworkPolicy" => object.and_then(decode::<NetworkPolicy>).map(|np| analyze_network_policies(&[np], &[])),
+        _ => None,
+    }
+    .unwrap_or_default()
+}
+
+fn decode<T: DeserializeOwned>(object: DynamicObject) -> Option<T> {
+    serde_json::from_value(serde_json::to_value(object).ok()?).ok()
+}
+
+/// Turns a batch of findings into an allow/deny response: denies with all
+/// blocking findings' messages joined into `result.message` if any finding
+/// meets `config`'s (possibly per-check-overridden) deny threshold,
+/// otherwise allows and surfaces every finding as a non-blocking warning.
+pub fn respond(base: AdmissionResponse, findings: &[Finding], config: &AdmissionConfig) -> AdmissionResponse {
+    let blocking: Vec<&Finding> = findings.iter().filter(|f| config.blocks(f)).collect();
+
+    if !blocking.is_empty() {
+        let reason = blocking.iter().map(|f| f.to_string()).collect::<Vec<_>>().join("; ");
+        return base.deny(reason);
+    }
+
+    let warnings: Vec<String> = findings.iter().map(|f| f.to_string()).collect();
+    base.warnings(warnings)
+}
@@ -1,90 +1,501 @@
-use k8s_openapi::api::core::v1::Pod;
-use k8s_openapi::api::rbac::v1::RoleBinding;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+
+use k8s_openapi::api::core::v1::{Pod, PodSecurityContext, SecurityContext};
 use k8s_openapi::api::networking::v1::NetworkPolicy;
+use k8s_openapi::api::rbac::v1::RoleBinding;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+
+use crate::finding::{Finding, Severity};
+
+/// The Pod Security Standards profile a control belongs to. `Restricted`
+/// is a superset of `Baseline`: every baseline control also applies under
+/// restricted enforcement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PssProfile {
+    Baseline,
+    Restricted,
+}
+
+impl fmt::Display for PssProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PssProfile::Baseline => "baseline",
+            PssProfile::Restricted => "restricted",
+        };
+        write!(f, "{}", s)
+    }
+}
 
 /// K01: Insecure Workload Configurations
-/// Analyze a Pod and return warning messages based on container security context settings.
-pub fn analyze_pod_insecure_workloads(pod: &Pod) -> Vec<String> {
-    let mut warnings = Vec::new();
+///
+/// Evaluates a Pod against the Pod Security Standards baseline and
+/// restricted profiles, checking both `podSpec.securityContext` and each
+/// container's `securityContext` (container-level settings override
+/// pod-level ones), across `containers`, `initContainers`, and
+/// `ephemeralContainers`. Emits one finding per violated control, tagged
+/// with the profile it breaks.
+pub fn analyze_pod_insecure_workloads(pod: &Pod) -> Vec<Finding> {
+    let mut findings = Vec::new();
     let pod_name = pod.metadata.name.clone().unwrap_or("<unknown>".into());
-    if let Some(spec) = &pod.spec {
-        for container in &spec.containers {
-            let container_name = container.name.clone();
-            if let Some(sc) = &container.security_context {
-                if let Some(run_as_non_root) = sc.run_as_non_root {
-                    if !run_as_non_root {
-                        warnings.push(format!(
-                            "[K01] Pod '{}' container '{}' may run as root (runAsNonRoot is false)",
-                            pod_name, container_name
-                        ));
-                    }
-                } else {
-                    warnings.push(format!(
-                        "[K01] Pod '{}' container '{}' has no runAsNonRoot setting",
-                        pod_name, container_name
-                    ));
-                }
-                if let Some(privileged) = sc.privileged {
-                    if privileged {
-                        warnings.push(format!(
-                            "[K01] Pod '{}' container '{}' is running in privileged mode",
-                            pod_name, container_name
-                        ));
-                    }
-                }
-            } else {
-                warnings.push(format!(
-                    "[K01] Pod '{}' container '{}' has no security context defined",
-                    pod_name, container_name
-                ));
-            }
+    let pod_uid = pod.metadata.uid.clone();
+    let namespace = pod.metadata.namespace.clone();
+
+    let Some(spec) = &pod.spec else {
+        return findings;
+    };
+
+    if spec.host_network == Some(true) {
+        findings.push(pod_finding(
+            &pod_name, &pod_uid, &namespace, Severity::Error, PssProfile::Baseline,
+            "hostNetwork is true",
+            "Set hostNetwork: false (or omit it).",
+        ));
+    }
+    if spec.host_pid == Some(true) {
+        findings.push(pod_finding(
+            &pod_name, &pod_uid, &namespace, Severity::Error, PssProfile::Baseline,
+            "hostPID is true",
+            "Set hostPID: false (or omit it).",
+        ));
+    }
+    if spec.host_ipc == Some(true) {
+        findings.push(pod_finding(
+            &pod_name, &pod_uid, &namespace, Severity::Error, PssProfile::Baseline,
+            "hostIPC is true",
+            "Set hostIPC: false (or omit it).",
+        ));
+    }
+
+    let pod_sc = spec.security_context.as_ref();
+
+    for container in &spec.containers {
+        findings.extend(evaluate_container(&pod_name, &pod_uid, &namespace, pod_sc, &container.name, container.security_context.as_ref()));
+    }
+    for container in spec.init_containers.iter().flatten() {
+        findings.extend(evaluate_container(&pod_name, &pod_uid, &namespace, pod_sc, &container.name, container.security_context.as_ref()));
+    }
+    for container in spec.ephemeral_containers.iter().flatten() {
+        findings.extend(evaluate_container(&pod_name, &pod_uid, &namespace, pod_sc, &container.name, container.security_context.as_ref()));
+    }
+
+    findings
+}
+
+/// Evaluates one container's (merged pod + container) security context
+/// against the baseline/restricted controls that apply per-container.
+/// Baseline violations are `Severity::Error` (the minimum bar for any
+/// workload); restricted violations are `Severity::Warning` (an
+/// opt-in, stricter hardening profile) so that a plain Pod with no
+/// `securityContext` at all doesn't trip a default deny threshold.
+fn evaluate_container(
+    pod_name: &str,
+    pod_uid: &Option<String>,
+    namespace: &Option<String>,
+    pod_sc: Option<&PodSecurityContext>,
+    container_name: &str,
+    container_sc: Option<&SecurityContext>,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    if container_sc.and_then(|sc| sc.privileged) == Some(true) {
+        findings.push(container_finding(
+            pod_name, pod_uid, namespace, container_name, Severity::Error, PssProfile::Baseline,
+            "is running in privileged mode",
+            "Set securityContext.privileged: false.",
+        ));
+    }
+
+    let run_as_non_root = container_sc
+        .and_then(|sc| sc.run_as_non_root)
+        .or_else(|| pod_sc.and_then(|sc| sc.run_as_non_root));
+    if run_as_non_root != Some(true) {
+        findings.push(container_finding(
+            pod_name, pod_uid, namespace, container_name, Severity::Warning, PssProfile::Restricted,
+            "does not require runAsNonRoot: true at the pod or container level",
+            "Set securityContext.runAsNonRoot: true at the pod or container level.",
+        ));
+    }
+
+    let allow_privilege_escalation = container_sc.and_then(|sc| sc.allow_privilege_escalation);
+    if allow_privilege_escalation != Some(false) {
+        findings.push(container_finding(
+            pod_name, pod_uid, namespace, container_name, Severity::Warning, PssProfile::Restricted,
+            "does not set allowPrivilegeEscalation: false",
+            "Set securityContext.allowPrivilegeEscalation: false.",
+        ));
+    }
+
+    let capabilities = container_sc.and_then(|sc| sc.capabilities.as_ref());
+    let drops_all = capabilities
+        .and_then(|c| c.drop.as_ref())
+        .map(|dropped| dropped.iter().any(|c| c == "ALL"))
+        .unwrap_or(false);
+    if !drops_all {
+        findings.push(container_finding(
+            pod_name, pod_uid, namespace, container_name, Severity::Warning, PssProfile::Restricted,
+            "does not drop the ALL capability",
+            "Set securityContext.capabilities.drop: [\"ALL\"].",
+        ));
+    }
+    if let Some(added) = capabilities.and_then(|c| c.add.as_ref()) {
+        let disallowed: Vec<&str> = added.iter().map(String::as_str).filter(|c| *c != "NET_BIND_SERVICE").collect();
+        if !disallowed.is_empty() {
+            findings.push(container_finding(
+                pod_name, pod_uid, namespace, container_name, Severity::Warning, PssProfile::Restricted,
+                &format!("adds disallowed capabilities: {}", disallowed.join(", ")),
+                "Only NET_BIND_SERVICE may be added under securityContext.capabilities.add.",
+            ));
         }
     }
-    warnings
+
+    let seccomp_type = container_sc
+        .and_then(|sc| sc.seccomp_profile.as_ref())
+        .or_else(|| pod_sc.and_then(|sc| sc.seccomp_profile.as_ref()))
+        .map(|profile| profile.type_.as_str());
+    if !matches!(seccomp_type, Some("RuntimeDefault") | Some("Localhost")) {
+        findings.push(container_finding(
+            pod_name, pod_uid, namespace, container_name, Severity::Warning, PssProfile::Restricted,
+            "does not set seccompProfile.type to RuntimeDefault or Localhost",
+            "Set securityContext.seccompProfile.type: RuntimeDefault.",
+        ));
+    }
+
+    if container_sc.and_then(|sc| sc.read_only_root_filesystem) != Some(true) {
+        findings.push(Finding {
+            check_id: "K01".into(),
+            group: "security".into(),
+            severity: Severity::Info,
+            message: format!("Pod '{}' container '{}' does not set readOnlyRootFilesystem: true (recommended)", pod_name, container_name),
+            resource_kind: "Pod".into(),
+            resource_name: pod_name.to_string(),
+            resource_uid: pod_uid.clone(),
+            namespace: namespace.clone(),
+            remediation: "Set securityContext.readOnlyRootFilesystem: true.".into(),
+            profile: None,
+        });
+    }
+
+    findings
+}
+
+fn pod_finding(
+    pod_name: &str,
+    pod_uid: &Option<String>,
+    namespace: &Option<String>,
+    severity: Severity,
+    profile: PssProfile,
+    violation: &str,
+    remediation: &str,
+) -> Finding {
+    Finding {
+        check_id: "K01".into(),
+        group: "security".into(),
+        severity,
+        message: format!("Pod '{}' {} ({} profile)", pod_name, violation, profile),
+        resource_kind: "Pod".into(),
+        resource_name: pod_name.to_string(),
+        resource_uid: pod_uid.clone(),
+        namespace: namespace.clone(),
+        remediation: remediation.into(),
+        profile: Some(profile.to_string()),
+    }
+}
+
+fn container_finding(
+    pod_name: &str,
+    pod_uid: &Option<String>,
+    namespace: &Option<String>,
+    container_name: &str,
+    severity: Severity,
+    profile: PssProfile,
+    violation: &str,
+    remediation: &str,
+) -> Finding {
+    Finding {
+        check_id: "K01".into(),
+        group: "security".into(),
+        severity,
+        message: format!("Pod '{}' container '{}' {} ({} profile)", pod_name, container_name, violation, profile),
+        resource_kind: "Pod".into(),
+        resource_name: pod_name.to_string(),
+        resource_uid: pod_uid.clone(),
+        namespace: namespace.clone(),
+        remediation: remediation.into(),
+        profile: Some(profile.to_string()),
+    }
 }
 
 /// K03: Overly Permissive RBAC Configurations
-/// Analyze a RoleBinding and return a warning if its role_ref indicates a high-privilege binding.
-pub fn analyze_role_binding(rb: &RoleBinding) -> Option<String> {
+/// Analyze a RoleBinding and return a finding if its role_ref indicates a high-privilege binding.
+pub fn analyze_role_binding(rb: &RoleBinding) -> Vec<Finding> {
     let rb_name = rb.metadata.name.clone().unwrap_or("<unknown>".into());
     let role_ref = &rb.role_ref; // role_ref is required.
     if role_ref.kind == "ClusterRole" && role_ref.name.to_lowercase().contains("cluster-admin") {
-        Some(format!(
-            "[K03] RoleBinding '{}' binds a high-privilege ClusterRole '{}'",
-            rb_name, role_ref.name
-        ))
+        vec![Finding {
+            check_id: "K03".into(),
+            group: "rbac".into(),
+            severity: Severity::Error,
+            message: format!(
+                "RoleBinding '{}' binds a high-privilege ClusterRole '{}'",
+                rb_name, role_ref.name
+            ),
+            resource_kind: "RoleBinding".into(),
+            resource_name: rb_name,
+            resource_uid: rb.metadata.uid.clone(),
+            namespace: rb.metadata.namespace.clone(),
+            remediation: "Bind a narrower Role or ClusterRole instead of cluster-admin.".into(),
+            profile: None,
+        }]
     } else {
-        None
+        Vec::new()
+    }
+}
+
+/// Returns whether a pod's labels satisfy a `LabelSelector`'s
+/// `matchLabels`/`matchExpressions`. An empty selector matches every pod,
+/// per the Kubernetes NetworkPolicy semantics.
+fn pod_matches_selector(pod_labels: &BTreeMap<String, String>, selector: &LabelSelector) -> bool {
+    if let Some(match_labels) = &selector.match_labels {
+        if !match_labels.iter().all(|(k, v)| pod_labels.get(k) == Some(v)) {
+            return false;
+        }
+    }
+    if let Some(expressions) = &selector.match_expressions {
+        for expr in expressions {
+            let matches = match expr.operator.as_str() {
+                "In" => expr
+                    .values
+                    .as_ref()
+                    .is_some_and(|vals| pod_labels.get(&expr.key).is_some_and(|v| vals.contains(v))),
+                "NotIn" => !expr
+                    .values
+                    .as_ref()
+                    .is_some_and(|vals| pod_labels.get(&expr.key).is_some_and(|v| vals.contains(v))),
+                "Exists" => pod_labels.contains_key(&expr.key),
+                "DoesNotExist" => !pod_labels.contains_key(&expr.key),
+                _ => true,
+            };
+            if !matches {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn network_policy_finding(
+    np_name: &str, np_uid: &Option<String>, namespace: &Option<String>,
+    severity: Severity, message: String, remediation: &str,
+) -> Finding {
+    Finding {
+        check_id: "K07".into(),
+        group: "network".into(),
+        severity,
+        message,
+        resource_kind: "NetworkPolicy".into(),
+        resource_name: np_name.to_string(),
+        resource_uid: np_uid.clone(),
+        namespace: namespace.clone(),
+        remediation: remediation.into(),
+        profile: None,
     }
 }
 
 /// K07: Missing Network Segmentation Controls
-/// Analyze a slice of NetworkPolicy objects and return a message.
-pub fn analyze_network_policies(nps: &[NetworkPolicy]) -> Option<String> {
-    if nps.is_empty() {
-        Some(String::from(
-            "[K07] No NetworkPolicies found. Consider implementing network segmentation controls.",
-        ))
-    } else {
-        Some(format!("[K07] Found {} NetworkPolicy object(s).", nps.len()))
+///
+/// Reasons about actual coverage rather than just counting policies: for
+/// each `NetworkPolicy`, determines which pods it selects and whether it
+/// governs Ingress and/or Egress, then reports any pod in `pods` that
+/// isn't matched by at least one Ingress policy and one Egress policy
+/// (implicitly allow-all). Also flags policies with an absent or empty
+/// ingress/egress rule list for the corresponding `policyTypes` entry
+/// (default-deny — informational; the field's absence and `[]` are
+/// equivalent per the NetworkPolicy API) versus a rule with an
+/// empty/missing `from`/`to` (allow-from-anywhere — warning).
+pub fn analyze_network_policies(nps: &[NetworkPolicy], pods: &[Pod]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut ingress_covered: HashSet<String> = HashSet::new();
+    let mut egress_covered: HashSet<String> = HashSet::new();
+    let empty_labels = BTreeMap::new();
+
+    for np in nps {
+        let np_name = np.metadata.name.clone().unwrap_or("<unknown>".into());
+        let np_uid = np.metadata.uid.clone();
+        let namespace = np.metadata.namespace.clone();
+        let Some(spec) = &np.spec else { continue };
+
+        let policy_types = spec.policy_types.clone().unwrap_or_else(|| {
+            let mut types = vec!["Ingress".to_string()];
+            if spec.egress.is_some() {
+                types.push("Egress".to_string());
+            }
+            types
+        });
+
+        let matched_pods: Vec<&Pod> = pods
+            .iter()
+            .filter(|pod| pod_matches_selector(pod.metadata.labels.as_ref().unwrap_or(&empty_labels), &spec.pod_selector))
+            .collect();
+
+        if policy_types.iter().any(|t| t == "Ingress") {
+            ingress_covered.extend(matched_pods.iter().filter_map(|p| p.metadata.name.clone()));
+            // An absent `ingress` list is semantically identical to an empty
+            // one (both deny all ingress) and is how manifests canonically
+            // spell "default-deny" — a real NetworkPolicy with no `ingress:`
+            // key at all must be treated the same as `ingress: []`.
+            match spec.ingress.as_deref() {
+                None | Some([]) => findings.push(network_policy_finding(
+                    &np_name, &np_uid, &namespace, Severity::Info,
+                    format!("NetworkPolicy '{}' default-denies all ingress traffic to its selected pods", np_name),
+                    "",
+                )),
+                Some(rules) if rules.iter().any(|r| r.from.as_ref().map(|f| f.is_empty()).unwrap_or(true)) => {
+                    findings.push(network_policy_finding(
+                        &np_name, &np_uid, &namespace, Severity::Warning,
+                        format!("NetworkPolicy '{}' has an ingress rule with no 'from' selector (allow-from-anywhere)", np_name),
+                        "Scope the ingress rule's 'from' to specific namespaces or pod selectors.",
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        if policy_types.iter().any(|t| t == "Egress") {
+            egress_covered.extend(matched_pods.iter().filter_map(|p| p.metadata.name.clone()));
+            match spec.egress.as_deref() {
+                None | Some([]) => findings.push(network_policy_finding(
+                    &np_name, &np_uid, &namespace, Severity::Info,
+                    format!("NetworkPolicy '{}' default-denies all egress traffic from its selected pods", np_name),
+                    "",
+                )),
+                Some(rules) if rules.iter().any(|r| r.to.as_ref().map(|t| t.is_empty()).unwrap_or(true)) => {
+                    findings.push(network_policy_finding(
+                        &np_name, &np_uid, &namespace, Severity::Warning,
+                        format!("NetworkPolicy '{}' has an egress rule with no 'to' selector (allow-to-anywhere)", np_name),
+                        "Scope the egress rule's 'to' to specific namespaces or pod selectors.",
+                    ))
+                }
+                _ => {}
+            }
+        }
     }
+
+    for pod in pods {
+        let pod_name = pod.metadata.name.clone().unwrap_or("<unknown>".into());
+        if !ingress_covered.contains(&pod_name) {
+            findings.push(Finding {
+                check_id: "K07".into(),
+                group: "network".into(),
+                severity: Severity::Warning,
+                message: format!("Pod '{}' is not covered by any Ingress NetworkPolicy (implicitly allow-all ingress)", pod_name),
+                resource_kind: "Pod".into(),
+                resource_name: pod_name.clone(),
+                resource_uid: pod.metadata.uid.clone(),
+                namespace: pod.metadata.namespace.clone(),
+                remediation: "Add a NetworkPolicy with an Ingress rule selecting this pod.".into(),
+                profile: None,
+            });
+        }
+        if !egress_covered.contains(&pod_name) {
+            findings.push(Finding {
+                check_id: "K07".into(),
+                group: "network".into(),
+                severity: Severity::Warning,
+                message: format!("Pod '{}' is not covered by any Egress NetworkPolicy (implicitly allow-all egress)", pod_name),
+                resource_kind: "Pod".into(),
+                resource_name: pod_name.clone(),
+                resource_uid: pod.metadata.uid.clone(),
+                namespace: pod.metadata.namespace.clone(),
+                remediation: "Add a NetworkPolicy with an Egress rule selecting this pod.".into(),
+                profile: None,
+            });
+        }
+    }
+
+    findings
 }
 
-/// K10: Outdated and Vulnerable Components (simplified)
-/// For each container in a Pod, return a message with its image.
-pub fn analyze_outdated_components(pod: &Pod) -> Vec<String> {
-    let mut warnings = Vec::new();
+/// Registries considered trustworthy by default when no allowlist is
+/// supplied by the caller.
+pub const DEFAULT_TRUSTED_REGISTRIES: &[&str] = &["docker.io", "gcr.io", "ghcr.io", "registry.k8s.io", "quay.io"];
+
+/// K10: Outdated and Vulnerable Components
+///
+/// Parses each container image into its `registry`/`repository`/`tag`/
+/// `digest` components and flags: mutable or missing tags (`latest` or no
+/// tag), tags with no pinning `@sha256:` digest, and repositories that
+/// aren't fully qualified with a registry host (implying Docker Hub) or
+/// whose registry isn't in `trusted_registries`. Applies to `containers`,
+/// `initContainers`, and `ephemeralContainers`.
+pub fn analyze_outdated_components(pod: &Pod, trusted_registries: &[&str]) -> Vec<Finding> {
+    let mut findings = Vec::new();
     let pod_name = pod.metadata.name.clone().unwrap_or("<unknown>".into());
-    if let Some(spec) = &pod.spec {
-        for container in &spec.containers {
-            if let Some(image) = &container.image {
-                warnings.push(format!(
-                    "[K10] Pod '{}' container '{}' is running image '{}'",
-                    pod_name, container.name, image
+    let pod_uid = pod.metadata.uid.clone();
+    let namespace = pod.metadata.namespace.clone();
+    let Some(spec) = &pod.spec else {
+        return findings;
+    };
+
+    let images = spec
+        .containers
+        .iter()
+        .map(|c| (&c.name, &c.image))
+        .chain(spec.init_containers.iter().flatten().map(|c| (&c.name, &c.image)))
+        .chain(spec.ephemeral_containers.iter().flatten().map(|c| (&c.name, &c.image)));
+
+    for (container_name, image) in images {
+        let Some(image) = image else { continue };
+        let image_ref = crate::image::parse(image);
+
+        if image_ref.digest.is_none() {
+            if image_ref.tag.is_none() || image_ref.tag.as_deref() == Some("latest") {
+                findings.push(image_finding(
+                    &pod_name, &pod_uid, &namespace, container_name, Severity::Warning,
+                    format!("uses image '{}' with a mutable or missing tag", image),
+                    "Pin the image to an immutable version tag and a @sha256: digest.",
+                ));
+            } else {
+                findings.push(image_finding(
+                    &pod_name, &pod_uid, &namespace, container_name, Severity::Warning,
+                    format!("uses image '{}' pinned by tag only, with no @sha256: digest", image),
+                    "Add a @sha256: digest so the image can't be silently replaced.",
                 ));
             }
         }
+
+        match &image_ref.registry {
+            None => findings.push(image_finding(
+                &pod_name, &pod_uid, &namespace, container_name, Severity::Warning,
+                format!("uses image '{}' with no registry host (implies Docker Hub)", image),
+                "Use a fully-qualified image reference, e.g. docker.io/library/<image>.",
+            )),
+            Some(registry) if !trusted_registries.contains(&registry.as_str()) => findings.push(image_finding(
+                &pod_name, &pod_uid, &namespace, container_name, Severity::Warning,
+                format!("uses image '{}' from untrusted registry '{}'", image, registry),
+                "Source the image from an allowlisted registry.",
+            )),
+            Some(_) => {}
+        }
     }
-    warnings
+
+    findings
 }
 
+fn image_finding(
+    pod_name: &str, pod_uid: &Option<String>, namespace: &Option<String>,
+    container_name: &str, severity: Severity, violation: String, remediation: &str,
+) -> Finding {
+    Finding {
+        check_id: "K10".into(),
+        group: "images".into(),
+        severity,
+        message: format!("Pod '{}' container '{}' {}", pod_name, container_name, violation),
+        resource_kind: "Pod".into(),
+        resource_name: pod_name.to_string(),
+        resource_uid: pod_uid.clone(),
+        namespace: namespace.clone(),
+        remediation: remediation.into(),
+        profile: None,
+    }
+}
@@ -0,0 +1,66 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/// Severity of a [`Finding`], ordered from least to most urgent so that
+/// `Severity::Error > Severity::Warning > Severity::Info`. This lets callers
+/// take the max over a `Vec<Finding>` to decide whether to fail a pipeline
+/// or set a process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    #[default]
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// A single, machine-readable result produced by a check.
+///
+/// Modeled after clusterlint-style checks: every rule has a stable
+/// `check_id` (e.g. "K01") and `group` (e.g. "security"), a human-readable
+/// `message`, the resource it was raised against, and `remediation`
+/// guidance describing how to fix it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub check_id: String,
+    pub group: String,
+    pub severity: Severity,
+    pub message: String,
+    pub resource_kind: String,
+    pub resource_name: String,
+    /// The resource's `metadata.uid`, when known. Used to key a finding
+    /// across watch events so a long-running monitor can diff successive
+    /// analysis passes and report only what's new or resolved.
+    pub resource_uid: Option<String>,
+    pub namespace: Option<String>,
+    pub remediation: String,
+    /// The enforcement profile this finding belongs to (e.g. "baseline" or
+    /// "restricted" for the Pod Security Standards), when the check has
+    /// one. `None` for checks that aren't tied to a profile.
+    pub profile: Option<String>,
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.check_id, self.message)
+    }
+}
+
+/// Returns the highest [`Severity`] present among `findings`, if any.
+/// Callers can use this to decide whether to filter, exit non-zero, or
+/// enforce a severity threshold.
+pub fn highest_severity(findings: &[Finding]) -> Option<Severity> {
+    findings.iter().map(|f| f.severity).max()
+}
@@ -0,0 +1,40 @@
+/// A parsed container image reference, split into the parts relevant to
+/// hygiene checks: which registry it came from, what repository/tag it
+/// names, and whether it's pinned by digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageRef {
+    /// The registry host, e.g. `docker.io` or `gcr.io`. `None` means the
+    /// reference had no registry component, which implies Docker Hub.
+    pub registry: Option<String>,
+    pub repository: String,
+    pub tag: Option<String>,
+    pub digest: Option<String>,
+}
+
+/// Parses a container image reference of the form
+/// `[registry/]repository[:tag][@digest]` into its components. This is a
+/// pragmatic subset of the full Docker reference grammar, sufficient to
+/// drive image-hygiene checks rather than to validate arbitrary input.
+pub fn parse(image: &str) -> ImageRef {
+    let (name_and_tag, digest) = match image.split_once('@') {
+        Some((name, digest)) => (name, Some(digest.to_string())),
+        None => (image, None),
+    };
+
+    let (registry, rest) = match name_and_tag.split_once('/') {
+        Some((first, rest)) if first.contains('.') || first.contains(':') || first == "localhost" => {
+            (Some(first.to_string()), rest)
+        }
+        _ => (None, name_and_tag),
+    };
+
+    // A ':' belongs to the tag only if it comes after the last '/' in the
+    // remaining path, otherwise it's a registry port that already got
+    // consumed above (or there's no tag at all).
+    let (repository, tag) = match rest.rfind(':') {
+        Some(idx) if !rest[idx + 1..].contains('/') => (rest[..idx].to_string(), Some(rest[idx + 1..].to_string())),
+        _ => (rest.to_string(), None),
+    };
+
+    ImageRef { registry, repository, tag, digest }
+}
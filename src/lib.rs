@@ -0,0 +1,7 @@
+pub mod admission;
+pub mod checks;
+pub mod finding;
+pub mod image;
+pub mod monitor;
+pub mod registry;
+pub mod report;
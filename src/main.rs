@@ -1,7 +1,89 @@
+use std::collections::HashMap;
+
+use anyhow::{anyhow, Result};
+use hk8s::admission::{self, AdmissionConfig};
+use hk8s::finding::Severity;
 use hk8s::monitor::run_monitoring_service;
-use anyhow::Result;
+use hk8s::report::ReportFormat;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    run_monitoring_service().await
+    let args = CliArgs::parse()?;
+    match args.mode.as_str() {
+        "admission" => {
+            let addr = "0.0.0.0:8443".parse()?;
+            let config = AdmissionConfig { deny_threshold: args.deny_threshold, check_overrides: args.check_overrides };
+            admission::serve(addr, &args.cert_path, &args.key_path, config).await;
+            Ok(())
+        }
+        "monitor" => run_monitoring_service(args.format).await,
+        other => Err(anyhow!("unknown --mode '{}', expected monitor|admission", other)),
+    }
+}
+
+struct CliArgs {
+    mode: String,
+    format: ReportFormat,
+    cert_path: String,
+    key_path: String,
+    deny_threshold: Severity,
+    check_overrides: HashMap<String, Option<Severity>>,
+}
+
+/// Parses `--mode <monitor|admission>`, `--format <text|json|sarif>`
+/// (monitor mode), `--cert`/`--key` (admission mode TLS material),
+/// `--deny-threshold <info|warning|error>` (admission mode), and
+/// `--check-override <CHECK_ID>=<info|warning|error|off>` (admission
+/// mode, repeatable) from the CLI args. `off` makes that check warn-only
+/// regardless of severity, overriding `--deny-threshold` for it.
+impl CliArgs {
+    fn parse() -> Result<Self> {
+        let mut mode = "monitor".to_string();
+        let mut format = ReportFormat::Text;
+        let mut cert_path = "tls/tls.crt".to_string();
+        let mut key_path = "tls/tls.key".to_string();
+        let mut deny_threshold = Severity::Error;
+        let mut check_overrides = HashMap::new();
+
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| args.next().ok_or_else(|| anyhow!("{} requires a value", flag));
+            match arg.as_str() {
+                "--mode" => mode = value("--mode")?,
+                "--format" => format = value("--format")?.parse().map_err(anyhow::Error::msg)?,
+                "--cert" => cert_path = value("--cert")?,
+                "--key" => key_path = value("--key")?,
+                "--deny-threshold" => deny_threshold = parse_severity(&value("--deny-threshold")?)?,
+                "--check-override" => {
+                    let (check_id, severity) = parse_check_override(&value("--check-override")?)?;
+                    check_overrides.insert(check_id, severity);
+                }
+                other => return Err(anyhow!("unknown argument '{}'", other)),
+            }
+        }
+
+        Ok(Self { mode, format, cert_path, key_path, deny_threshold, check_overrides })
+    }
+}
+
+fn parse_severity(s: &str) -> Result<Severity> {
+    match s.to_lowercase().as_str() {
+        "info" => Ok(Severity::Info),
+        "warning" => Ok(Severity::Warning),
+        "error" => Ok(Severity::Error),
+        other => Err(anyhow!("unknown severity '{}', expected info|warning|error", other)),
+    }
+}
+
+/// Parses one `--check-override` value of the form `<CHECK_ID>=<SEVERITY>`,
+/// e.g. `K01=warning` or `K01=off`.
+fn parse_check_override(s: &str) -> Result<(String, Option<Severity>)> {
+    let (check_id, severity) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("--check-override expects '<CHECK_ID>=<info|warning|error|off>', got '{}'", s))?;
+    let severity = match severity.to_lowercase().as_str() {
+        "off" => None,
+        other => Some(parse_severity(other)?),
+    };
+    Ok((check_id.to_string(), severity))
 }
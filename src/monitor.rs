@@ -1,84 +1,169 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
 use anyhow::Result;
-use kube::{api::{Api, ListParams}, Client};
+use futures::StreamExt;
 use k8s_openapi::api::core::v1::Pod;
-use k8s_openapi::api::rbac::v1::RoleBinding;
 use k8s_openapi::api::networking::v1::NetworkPolicy;
-use crate::checks::{analyze_pod_insecure_workloads, analyze_role_binding, analyze_network_policies, analyze_outdated_components};
-use tokio::time::{sleep, Duration};
-
-pub async fn check_insecure_workloads(client: Client, namespace: &str) -> Result<()> {
-    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
-    let lp = ListParams::default();
-    let pod_list = pods.list(&lp).await?;
-    for p in pod_list.items {
-        let warnings = analyze_pod_insecure_workloads(&p);
-        for w in warnings {
-            println!("{}", w);
+use k8s_openapi::api::rbac::v1::RoleBinding;
+use kube::runtime::{watcher, WatchStreamExt};
+use kube::{Api, Client, Resource, ResourceExt};
+use tokio::sync::{Mutex, Notify};
+use tokio::time::sleep;
+
+use crate::checks::{analyze_network_policies, analyze_outdated_components, analyze_pod_insecure_workloads, analyze_role_binding};
+use crate::finding::Finding;
+use crate::report::{ReportFormat, Reporter};
+
+/// Fixed delay between a change notification and the analysis pass it
+/// triggers; the delay is not extended by later events. `Applied` events
+/// arriving within that window (e.g. a rollout touching many Pods at once)
+/// are picked up by the same pass instead of each triggering its own.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A live, per-namespace, per-name mirror of one resource kind, kept up to
+/// date from a `watcher` event stream instead of being re-listed from the
+/// API server on a timer.
+type Index<T> = Arc<Mutex<HashMap<String, HashMap<String, T>>>>;
+
+fn new_index<T>() -> Index<T> {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+async fn apply_event<T>(index: &Index<T>, event: watcher::Event<T>)
+where
+    T: Resource,
+{
+    let mut index = index.lock().await;
+    match event {
+        watcher::Event::Applied(obj) => {
+            let ns = obj.namespace().unwrap_or_default();
+            let name = obj.name_any();
+            index.entry(ns).or_default().insert(name, obj);
+        }
+        watcher::Event::Deleted(obj) => {
+            let ns = obj.namespace().unwrap_or_default();
+            let name = obj.name_any();
+            if let Some(by_name) = index.get_mut(&ns) {
+                by_name.remove(&name);
+            }
+        }
+        watcher::Event::Restarted(objs) => {
+            index.clear();
+            for obj in objs {
+                let ns = obj.namespace().unwrap_or_default();
+                let name = obj.name_any();
+                index.entry(ns).or_default().insert(name, obj);
+            }
         }
     }
-    Ok(())
 }
 
-pub async fn check_overly_permissive_rbac(client: Client, namespace: &str) -> Result<()> {
-    let role_bindings: Api<RoleBinding> = Api::namespaced(client.clone(), namespace);
-    let lp = ListParams::default();
-    let rb_list = role_bindings.list(&lp).await?;
-    for rb in rb_list.items {
-        if let Some(msg) = analyze_role_binding(&rb) {
-            println!("{}", msg);
+/// Watches one resource kind, folding every event into `index` and waking
+/// `notify` so the analysis loop knows to re-evaluate checks. Runs until
+/// the watch stream ends (normally: forever).
+async fn watch_into_index<T>(api: Api<T>, index: Index<T>, notify: Arc<Notify>)
+where
+    T: Resource<DynamicType = ()> + Clone + std::fmt::Debug + Send + Sync + for<'de> serde::Deserialize<'de> + 'static,
+{
+    let mut stream = Box::pin(watcher(api, watcher::Config::default()).default_backoff());
+    while let Some(event) = stream.next().await {
+        match event {
+            Ok(event) => {
+                apply_event(&index, event).await;
+                notify.notify_one();
+            }
+            Err(e) => eprintln!("watch error: {:?}", e),
         }
     }
-    Ok(())
 }
 
-pub async fn check_network_policies(client: Client, namespace: &str) -> Result<()> {
-    let netpols: Api<NetworkPolicy> = Api::namespaced(client.clone(), namespace);
-    let lp = ListParams::default();
-    let netpol_list = netpols.list(&lp).await?;
-    if let Some(msg) = analyze_network_policies(&netpol_list.items) {
-        println!("{}", msg);
-    }
-    Ok(())
+async fn snapshot<T: Clone>(index: &Index<T>, namespace: &str) -> Vec<T> {
+    let index = index.lock().await;
+    index
+        .get(namespace)
+        .map(|by_name| by_name.values().cloned().collect())
+        .unwrap_or_default()
 }
 
-pub async fn check_outdated_components(client: Client, namespace: &str) -> Result<()> {
-    // For this demo, we assume Airflow webserver pods are labeled with "component=webserver"
-    let lp = ListParams::default().labels("component=webserver");
-    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
-    let pod_list = pods.list(&lp).await?;
-    for p in pod_list.items {
-        let warnings = analyze_outdated_components(&p);
-        for w in warnings {
-            println!("{}", w);
-        }
-    }
-    Ok(())
+/// Keys a finding by the check that raised it, the resource it was raised
+/// against, and its message, so successive analysis passes can be diffed
+/// to report only what's new or resolved. The message is part of the key
+/// because a single check (e.g. K01 or K07) routinely emits more than one
+/// distinct finding for the same resource; keying on `(check_id,
+/// resource_uid)` alone would collapse those into one slot and silently
+/// drop a newly-introduced violation once any finding from that check had
+/// already been seen for the resource.
+fn finding_key(finding: &Finding) -> (String, String, String) {
+    (finding.check_id.clone(), finding.resource_uid.clone().unwrap_or_default(), finding.message.clone())
 }
 
-pub async fn run_monitoring_service() -> Result<()> {
+pub async fn run_monitoring_service(format: ReportFormat) -> Result<()> {
     let client = Client::try_default().await?;
     let namespace = "airflow"; // adjust as needed
+    let reporter = Reporter::new(format);
+
+    let pods_index: Index<Pod> = new_index();
+    let role_bindings_index: Index<RoleBinding> = new_index();
+    let network_policies_index: Index<NetworkPolicy> = new_index();
+
+    let notify = Arc::new(Notify::new());
+
+    tokio::spawn(watch_into_index(
+        Api::<Pod>::namespaced(client.clone(), namespace),
+        pods_index.clone(),
+        notify.clone(),
+    ));
+    tokio::spawn(watch_into_index(
+        Api::<RoleBinding>::namespaced(client.clone(), namespace),
+        role_bindings_index.clone(),
+        notify.clone(),
+    ));
+    tokio::spawn(watch_into_index(
+        Api::<NetworkPolicy>::namespaced(client.clone(), namespace),
+        network_policies_index.clone(),
+        notify.clone(),
+    ));
 
-    println!("Starting continuous Kubernetes monitoring service in namespace '{}'", namespace);
+    eprintln!("Watching namespace '{}' for security-relevant changes", namespace);
+
+    let mut previous: HashSet<(String, String, String)> = HashSet::new();
 
     loop {
-        println!("--- Running security checks ---");
+        notify.notified().await;
+        sleep(DEBOUNCE).await;
 
-        let (res1, res2, res3, res4) = tokio::join!(
-            check_insecure_workloads(client.clone(), namespace),
-            check_overly_permissive_rbac(client.clone(), namespace),
-            check_network_policies(client.clone(), namespace),
-            check_outdated_components(client.clone(), namespace),
-        );
+        let pods = snapshot(&pods_index, namespace).await;
+        let role_bindings = snapshot(&role_bindings_index, namespace).await;
+        let network_policies = snapshot(&network_policies_index, namespace).await;
 
-        if let Err(e) = res1 { eprintln!("Error in insecure workloads check: {:?}", e); }
-        if let Err(e) = res2 { eprintln!("Error in RBAC check: {:?}", e); }
-        if let Err(e) = res3 { eprintln!("Error in network policies check: {:?}", e); }
-        if let Err(e) = res4 { eprintln!("Error in outdated components check: {:?}", e); }
+        let mut findings = Vec::new();
+        for pod in &pods {
+            findings.extend(analyze_pod_insecure_workloads(pod));
+            findings.extend(analyze_outdated_components(pod, crate::checks::DEFAULT_TRUSTED_REGISTRIES));
+        }
+        for rb in &role_bindings {
+            findings.extend(analyze_role_binding(rb));
+        }
+        findings.extend(analyze_network_policies(&network_policies, &pods));
 
-        println!("--- Security checks complete ---\n");
+        let current_keys: HashSet<(String, String, String)> = findings.iter().map(finding_key).collect();
 
-        sleep(Duration::from_secs(30)).await;
+        let new_or_changed: Vec<Finding> = findings
+            .into_iter()
+            .filter(|f| !previous.contains(&finding_key(f)))
+            .collect();
+
+        if !new_or_changed.is_empty() {
+            reporter.write(&new_or_changed, &mut std::io::stdout())?;
+        }
+
+        let resolved_count = previous.difference(&current_keys).count();
+        if resolved_count > 0 {
+            eprintln!("{} previously reported finding(s) no longer apply", resolved_count);
+        }
+
+        previous = current_keys;
     }
 }
-
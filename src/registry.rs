@@ -0,0 +1,41 @@
+/// Static metadata describing a check, independent of any particular
+/// finding it has produced. Used to populate rule catalogs such as a SARIF
+/// `tool.driver.rules` array.
+pub struct CheckMeta {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub group: &'static str,
+    pub description: &'static str,
+}
+
+/// The full set of checks hk8s knows how to run.
+pub const CHECKS: &[CheckMeta] = &[
+    CheckMeta {
+        id: "K01",
+        name: "insecure-workload",
+        group: "security",
+        description: "Flags Pods with missing or insecure container security contexts.",
+    },
+    CheckMeta {
+        id: "K03",
+        name: "overly-permissive-rbac",
+        group: "rbac",
+        description: "Flags RoleBindings to a ClusterRole whose name contains cluster-admin; other ClusterRoles are not flagged.",
+    },
+    CheckMeta {
+        id: "K07",
+        name: "missing-network-segmentation",
+        group: "network",
+        description: "Flags namespaces with missing or ineffective NetworkPolicy coverage.",
+    },
+    CheckMeta {
+        id: "K10",
+        name: "outdated-component",
+        group: "images",
+        description: "Flags containers running images that may be outdated or unpinned.",
+    },
+];
+
+pub fn all_checks() -> &'static [CheckMeta] {
+    CHECKS
+}
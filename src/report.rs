@@ -0,0 +1,125 @@
+use std::io::Write;
+use std::str::FromStr;
+
+use serde_json::json;
+
+use crate::finding::{Finding, Severity};
+use crate::registry::all_checks;
+
+/// Output format for a batch of findings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// One human-readable line per finding (the historical behavior).
+    Text,
+    /// Newline-delimited JSON, one `Finding` per line.
+    Json,
+    /// A SARIF 2.1.0 run, suitable for code-scanning dashboards.
+    Sarif,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(ReportFormat::Text),
+            "json" => Ok(ReportFormat::Json),
+            "sarif" => Ok(ReportFormat::Sarif),
+            other => Err(format!("unknown report format '{}', expected text|json|sarif", other)),
+        }
+    }
+}
+
+/// Serializes [`Finding`]s into the configured [`ReportFormat`] and writes
+/// them to a sink, so the same check results can feed a terminal, a log
+/// aggregator, or a SARIF-consuming code-scanning dashboard.
+pub struct Reporter {
+    format: ReportFormat,
+}
+
+impl Reporter {
+    pub fn new(format: ReportFormat) -> Self {
+        Self { format }
+    }
+
+    pub fn write(&self, findings: &[Finding], out: &mut impl Write) -> std::io::Result<()> {
+        match self.format {
+            ReportFormat::Text => {
+                for finding in findings {
+                    writeln!(out, "{}", finding)?;
+                }
+                Ok(())
+            }
+            ReportFormat::Json => {
+                for finding in findings {
+                    writeln!(out, "{}", serde_json::to_string(finding)?)?;
+                }
+                Ok(())
+            }
+            ReportFormat::Sarif => writeln!(out, "{}", serde_json::to_string_pretty(&to_sarif(findings))?),
+        }
+    }
+}
+
+fn severity_to_sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// Builds a SARIF 2.1.0 `run`, with `tool.driver.rules` populated from the
+/// static check registry so every rule is documented even if it produced no
+/// findings this run.
+fn to_sarif(findings: &[Finding]) -> serde_json::Value {
+    let rules: Vec<_> = all_checks()
+        .iter()
+        .map(|check| {
+            json!({
+                "id": check.id,
+                "name": check.name,
+                "shortDescription": { "text": check.description },
+                "properties": { "group": check.group },
+            })
+        })
+        .collect();
+
+    let results: Vec<_> = findings
+        .iter()
+        .map(|finding| {
+            let location = format!(
+                "{}/{}/{}",
+                finding.namespace.as_deref().unwrap_or("<cluster>"),
+                finding.resource_kind,
+                finding.resource_name
+            );
+            json!({
+                "ruleId": finding.check_id,
+                "level": severity_to_sarif_level(finding.severity),
+                "message": { "text": finding.message },
+                "locations": [{
+                    "logicalLocations": [{
+                        "fullyQualifiedName": location,
+                        "kind": finding.resource_kind,
+                    }]
+                }]
+            })
+        })
+        .collect();
+
+    json!({
+        "version": "2.1.0",
+        "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "hk8s",
+                    "informationUri": "https://github.com/AnthKta/hk8s",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
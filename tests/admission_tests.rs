@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+
+use hk8s::admission::{findings_for, respond, AdmissionConfig};
+use hk8s::finding::{Finding, Severity};
+use k8s_openapi::api::core::v1::{Container, Pod, PodSpec, SecurityContext};
+use k8s_openapi::api::rbac::v1::{RoleBinding, RoleRef};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use kube::core::admission::AdmissionResponse;
+use kube::core::DynamicObject;
+
+fn as_dynamic_object<T: serde::Serialize>(value: &T) -> DynamicObject {
+    serde_json::from_value(serde_json::to_value(value).unwrap()).unwrap()
+}
+
+fn finding(check_id: &str, severity: Severity) -> Finding {
+    Finding {
+        check_id: check_id.into(),
+        group: "security".into(),
+        severity,
+        message: format!("{} violation", check_id),
+        resource_kind: "Pod".into(),
+        resource_name: "web".into(),
+        resource_uid: Some("uid-1".into()),
+        namespace: Some("airflow".into()),
+        remediation: "fix it".into(),
+        profile: None,
+    }
+}
+
+#[test]
+fn test_findings_for_pod_runs_pod_checks() {
+    let pod = Pod {
+        metadata: ObjectMeta { name: Some("web".into()), ..Default::default() },
+        spec: Some(PodSpec {
+            containers: vec![Container {
+                name: "c".into(),
+                security_context: Some(SecurityContext { privileged: Some(true), ..Default::default() }),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let findings = findings_for("Pod", Some(as_dynamic_object(&pod)));
+    assert!(findings.iter().any(|f| f.check_id == "K01" && f.message.contains("privileged mode")));
+}
+
+#[test]
+fn test_findings_for_role_binding_runs_rbac_check() {
+    let rb = RoleBinding {
+        metadata: ObjectMeta { name: Some("rb".into()), ..Default::default() },
+        role_ref: RoleRef {
+            api_group: "rbac.authorization.k8s.io".into(),
+            kind: "ClusterRole".into(),
+            name: "cluster-admin".into(),
+        },
+        ..Default::default()
+    };
+    let findings = findings_for("RoleBinding", Some(as_dynamic_object(&rb)));
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].check_id, "K03");
+}
+
+#[test]
+fn test_findings_for_unknown_kind_returns_nothing() {
+    let pod = Pod::default();
+    assert!(findings_for("ConfigMap", Some(as_dynamic_object(&pod))).is_empty());
+}
+
+#[test]
+fn test_findings_for_no_object_returns_nothing() {
+    assert!(findings_for("Pod", None).is_empty());
+}
+
+#[test]
+fn test_respond_allows_and_warns_below_threshold() {
+    let config = AdmissionConfig { deny_threshold: Severity::Error, ..Default::default() };
+    let findings = vec![finding("K10", Severity::Warning)];
+    let response = respond(AdmissionResponse::default(), &findings, &config);
+    assert!(response.allowed);
+}
+
+#[test]
+fn test_respond_denies_at_or_above_threshold() {
+    let config = AdmissionConfig { deny_threshold: Severity::Error, ..Default::default() };
+    let findings = vec![finding("K01", Severity::Error)];
+    let response = respond(AdmissionResponse::default(), &findings, &config);
+    assert!(!response.allowed);
+}
+
+#[test]
+fn test_respond_per_check_override_can_lower_a_checks_threshold() {
+    let mut check_overrides = HashMap::new();
+    check_overrides.insert("K01".to_string(), Some(Severity::Info));
+    let config = AdmissionConfig { deny_threshold: Severity::Error, check_overrides };
+    let findings = vec![finding("K01", Severity::Warning)];
+    let response = respond(AdmissionResponse::default(), &findings, &config);
+    assert!(!response.allowed);
+}
+
+#[test]
+fn test_respond_per_check_override_of_none_never_blocks() {
+    let mut check_overrides = HashMap::new();
+    check_overrides.insert("K01".to_string(), None);
+    let config = AdmissionConfig { deny_threshold: Severity::Error, check_overrides };
+    let findings = vec![finding("K01", Severity::Error)];
+    let response = respond(AdmissionResponse::default(), &findings, &config);
+    assert!(response.allowed);
+}
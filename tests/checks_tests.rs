@@ -1,8 +1,22 @@
 use hk8s::checks::*;
+use hk8s::finding::Severity;
 use k8s_openapi::api::core::v1::{Pod, PodSpec, Container, SecurityContext};
-use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::{LabelSelector, ObjectMeta};
 use k8s_openapi::api::rbac::v1::{RoleBinding, RoleRef};
-use k8s_openapi::api::networking::v1::NetworkPolicy;
+use k8s_openapi::api::networking::v1::{NetworkPolicy, NetworkPolicyIngressRule, NetworkPolicySpec};
+use std::collections::BTreeMap;
+
+fn labeled_pod(name: &str, labels: &[(&str, &str)]) -> Pod {
+    Pod {
+        metadata: ObjectMeta {
+            name: Some(name.into()),
+            labels: Some(labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()),
+            ..Default::default()
+        },
+        spec: Some(PodSpec { containers: vec![Container { name: "c".into(), ..Default::default() }], ..Default::default() }),
+        ..Default::default()
+    }
+}
 
 #[test]
 fn test_pod_no_security_context() {
@@ -18,9 +32,14 @@ fn test_pod_no_security_context() {
         }),
         ..Default::default()
     };
-    let warnings = analyze_pod_insecure_workloads(&pod);
-    assert!(warnings.contains(&"[K01] Pod 'pod-no-sc' container 'container1' has no security context defined".into()));
-    assert_eq!(warnings.len(), 1);
+    let findings = analyze_pod_insecure_workloads(&pod);
+    assert!(findings.iter().all(|f| f.check_id == "K01"));
+    assert!(findings.iter().any(|f| f.message.contains("runAsNonRoot") && f.profile.as_deref() == Some("restricted")));
+    assert!(findings.iter().any(|f| f.message.contains("allowPrivilegeEscalation")));
+    assert!(findings.iter().any(|f| f.message.contains("ALL capability")));
+    assert!(findings.iter().any(|f| f.message.contains("seccompProfile")));
+    assert!(findings.iter().any(|f| f.message.contains("readOnlyRootFilesystem") && f.severity == Severity::Info));
+    assert!(!findings.iter().any(|f| f.message.contains("privileged mode")));
 }
 
 #[test]
@@ -42,9 +61,9 @@ fn test_pod_missing_run_as_non_root() {
         }),
         ..Default::default()
     };
-    let warnings = analyze_pod_insecure_workloads(&pod);
-    assert!(warnings.contains(&"[K01] Pod 'pod-missing-run-as' container 'container1' has no runAsNonRoot setting".into()));
-    assert_eq!(warnings.len(), 1);
+    let findings = analyze_pod_insecure_workloads(&pod);
+    assert!(findings.iter().any(|f| f.message.contains("does not require runAsNonRoot")));
+    assert!(!findings.iter().any(|f| f.message.contains("privileged mode")));
 }
 
 #[test]
@@ -66,10 +85,42 @@ fn test_pod_run_as_non_root_false_and_privileged_true() {
         }),
         ..Default::default()
     };
-    let warnings = analyze_pod_insecure_workloads(&pod);
-    assert!(warnings.contains(&"[K01] Pod 'pod-insecure' container 'container1' may run as root (runAsNonRoot is false)".into()));
-    assert!(warnings.contains(&"[K01] Pod 'pod-insecure' container 'container1' is running in privileged mode".into()));
-    assert_eq!(warnings.len(), 2);
+    let findings = analyze_pod_insecure_workloads(&pod);
+    assert!(findings.iter().any(|f| f.message.contains("does not require runAsNonRoot")));
+    assert!(findings.iter().any(|f| f.message.contains("privileged mode") && f.profile.as_deref() == Some("baseline")));
+}
+
+#[test]
+fn test_pod_fully_compliant_restricted() {
+    let sc = SecurityContext {
+        run_as_non_root: Some(true),
+        privileged: Some(false),
+        allow_privilege_escalation: Some(false),
+        read_only_root_filesystem: Some(true),
+        capabilities: Some(k8s_openapi::api::core::v1::Capabilities {
+            drop: Some(vec!["ALL".into()]),
+            add: None,
+        }),
+        seccomp_profile: Some(k8s_openapi::api::core::v1::SeccompProfile {
+            type_: "RuntimeDefault".into(),
+            localhost_profile: None,
+        }),
+        ..Default::default()
+    };
+    let pod = Pod {
+        metadata: ObjectMeta { name: Some("pod-compliant".into()), ..Default::default() },
+        spec: Some(PodSpec {
+            containers: vec![Container {
+                name: "container1".into(),
+                security_context: Some(sc),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let findings = analyze_pod_insecure_workloads(&pod);
+    assert!(findings.is_empty());
 }
 
 #[test]
@@ -83,9 +134,11 @@ fn test_role_binding_cluster_admin() {
         },
         ..Default::default()
     };
-    let result = analyze_role_binding(&rb);
-    assert!(result.is_some());
-    assert!(result.unwrap().contains("cluster-admin"));
+    let findings = analyze_role_binding(&rb);
+    assert_eq!(findings.len(), 1);
+    assert_eq!(findings[0].check_id, "K03");
+    assert_eq!(findings[0].severity, Severity::Error);
+    assert!(findings[0].message.contains("cluster-admin"));
 }
 
 #[test]
@@ -99,8 +152,8 @@ fn test_role_binding_non_admin_cluster_role() {
         },
         ..Default::default()
     };
-    let result = analyze_role_binding(&rb);
-    assert!(result.is_none());
+    let findings = analyze_role_binding(&rb);
+    assert!(findings.is_empty());
 }
 
 #[test]
@@ -114,86 +167,150 @@ fn test_role_binding_role_instead_of_cluster_role() {
         },
         ..Default::default()
     };
-    let result = analyze_role_binding(&rb);
-    assert!(result.is_none());
+    let findings = analyze_role_binding(&rb);
+    assert!(findings.is_empty());
 }
 
 #[test]
-fn test_network_policies_empty() {
+fn test_network_policies_empty_flags_every_pod_uncovered() {
     let policies: Vec<NetworkPolicy> = Vec::new();
-    let result = analyze_network_policies(&policies);
-    assert!(result.is_some());
-    assert!(result.unwrap().contains("No NetworkPolicies found"));
+    let pods = vec![labeled_pod("web", &[("app", "web")])];
+    let findings = analyze_network_policies(&policies, &pods);
+    assert!(findings.iter().any(|f| f.message.contains("not covered by any Ingress NetworkPolicy")));
+    assert!(findings.iter().any(|f| f.message.contains("not covered by any Egress NetworkPolicy")));
 }
 
 #[test]
-fn test_network_policies_one() {
+fn test_network_policy_covers_matching_pod() {
     let np = NetworkPolicy {
-        metadata: ObjectMeta { name: Some("np1".into()), ..Default::default() },
+        metadata: ObjectMeta { name: Some("allow-web".into()), ..Default::default() },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: LabelSelector {
+                match_labels: Some(BTreeMap::from([("app".to_string(), "web".to_string())])),
+                ..Default::default()
+            },
+            policy_types: Some(vec!["Ingress".into()]),
+            ingress: Some(vec![NetworkPolicyIngressRule {
+                from: Some(vec![]),
+                ports: None,
+            }]),
+            egress: None,
+        }),
         ..Default::default()
     };
-    let policies = vec![np];
-    let result = analyze_network_policies(&policies);
-    assert!(result.is_some());
-    assert!(result.unwrap().contains("Found 1 NetworkPolicy"));
+    let pods = vec![labeled_pod("web", &[("app", "web")])];
+    let findings = analyze_network_policies(&vec![np], &pods);
+    assert!(!findings.iter().any(|f| f.message.contains("not covered by any Ingress")));
+    assert!(findings.iter().any(|f| f.message.contains("not covered by any Egress")));
 }
 
 #[test]
-fn test_network_policies_multiple() {
-    let np1 = NetworkPolicy {
-        metadata: ObjectMeta { name: Some("np1".into()), ..Default::default() },
-        ..Default::default()
-    };
-    let np2 = NetworkPolicy {
-        metadata: ObjectMeta { name: Some("np2".into()), ..Default::default() },
+fn test_network_policy_empty_ingress_rules_is_default_deny() {
+    let np = NetworkPolicy {
+        metadata: ObjectMeta { name: Some("deny-all".into()), ..Default::default() },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: LabelSelector::default(),
+            policy_types: Some(vec!["Ingress".into()]),
+            ingress: Some(vec![]),
+            egress: None,
+        }),
         ..Default::default()
     };
-    let np3 = NetworkPolicy {
-        metadata: ObjectMeta { name: Some("np3".into()), ..Default::default() },
+    let pods = vec![labeled_pod("web", &[("app", "web")])];
+    let findings = analyze_network_policies(&vec![np], &pods);
+    assert!(findings.iter().any(|f| f.severity == Severity::Info && f.message.contains("default-denies all ingress")));
+}
+
+#[test]
+fn test_network_policy_absent_ingress_field_is_default_deny() {
+    let np = NetworkPolicy {
+        metadata: ObjectMeta { name: Some("deny-all".into()), ..Default::default() },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: LabelSelector::default(),
+            policy_types: Some(vec!["Ingress".into()]),
+            ingress: None,
+            egress: None,
+        }),
         ..Default::default()
     };
-    let policies = vec![np1, np2, np3];
-    let result = analyze_network_policies(&policies);
-    assert!(result.is_some());
-    assert!(result.unwrap().contains("Found 3 NetworkPolicy"));
+    let pods = vec![labeled_pod("web", &[("app", "web")])];
+    let findings = analyze_network_policies(&vec![np], &pods);
+    assert!(findings.iter().any(|f| f.severity == Severity::Info && f.message.contains("default-denies all ingress")));
 }
 
 #[test]
-fn test_outdated_component_with_versioned_image() {
-    let pod = Pod {
-        metadata: ObjectMeta { name: Some("airflow-web-1".into()), ..Default::default() },
-        spec: Some(PodSpec {
-            containers: vec![Container {
-                name: "web".into(),
-                image: Some("apache/airflow:2.5.1".into()),
-                ..Default::default()
-            }],
-            ..Default::default()
+fn test_network_policy_empty_from_is_allow_from_anywhere() {
+    let np = NetworkPolicy {
+        metadata: ObjectMeta { name: Some("allow-any".into()), ..Default::default() },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: LabelSelector::default(),
+            policy_types: Some(vec!["Ingress".into()]),
+            ingress: Some(vec![NetworkPolicyIngressRule { from: None, ports: None }]),
+            egress: None,
         }),
         ..Default::default()
     };
-    let warnings = analyze_outdated_components(&pod);
-    assert_eq!(warnings.len(), 1);
-    assert!(warnings[0].contains("apache/airflow:2.5.1"));
+    let pods = vec![labeled_pod("web", &[("app", "web")])];
+    let findings = analyze_network_policies(&vec![np], &pods);
+    assert!(findings.iter().any(|f| f.severity == Severity::Warning && f.message.contains("allow-from-anywhere")));
 }
 
-#[test]
-fn test_outdated_component_with_latest_image() {
-    let pod = Pod {
-        metadata: ObjectMeta { name: Some("airflow-web-2".into()), ..Default::default() },
+fn image_pod(image: &str) -> Pod {
+    Pod {
+        metadata: ObjectMeta { name: Some("airflow-web".into()), ..Default::default() },
         spec: Some(PodSpec {
             containers: vec![Container {
                 name: "web".into(),
-                image: Some("apache/airflow:latest".into()),
+                image: Some(image.into()),
                 ..Default::default()
             }],
             ..Default::default()
         }),
         ..Default::default()
-    };
-    let warnings = analyze_outdated_components(&pod);
-    assert_eq!(warnings.len(), 1);
-    assert!(warnings[0].contains("apache/airflow:latest"));
+    }
+}
+
+#[test]
+fn test_outdated_component_fully_qualified_digest_pinned_image() {
+    let pod = image_pod("docker.io/apache/airflow@sha256:abcd1234");
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert!(findings.is_empty());
+}
+
+#[test]
+fn test_outdated_component_with_versioned_tag_no_digest() {
+    let pod = image_pod("docker.io/apache/airflow:2.5.1");
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert_eq!(findings.len(), 1);
+    assert!(findings[0].message.contains("no @sha256: digest"));
+}
+
+#[test]
+fn test_outdated_component_with_latest_image() {
+    let pod = image_pod("docker.io/apache/airflow:latest");
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert!(findings.iter().any(|f| f.message.contains("mutable or missing tag")));
+}
+
+#[test]
+fn test_outdated_component_no_tag() {
+    let pod = image_pod("docker.io/apache/airflow");
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert!(findings.iter().any(|f| f.message.contains("mutable or missing tag")));
+}
+
+#[test]
+fn test_outdated_component_unqualified_repository_implies_docker_hub() {
+    let pod = image_pod("apache/airflow:2.5.1");
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert!(findings.iter().any(|f| f.message.contains("no registry host")));
+}
+
+#[test]
+fn test_outdated_component_untrusted_registry() {
+    let pod = image_pod("evil-registry.example.com/apache/airflow@sha256:abcd1234");
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert!(findings.iter().any(|f| f.message.contains("untrusted registry 'evil-registry.example.com'")));
 }
 
 #[test]
@@ -210,7 +327,6 @@ fn test_outdated_component_no_image() {
         }),
         ..Default::default()
     };
-    let warnings = analyze_outdated_components(&pod);
-    assert!(warnings.is_empty());
+    let findings = analyze_outdated_components(&pod, DEFAULT_TRUSTED_REGISTRIES);
+    assert!(findings.is_empty());
 }
-
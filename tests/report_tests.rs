@@ -0,0 +1,93 @@
+use hk8s::finding::{Finding, Severity};
+use hk8s::report::{ReportFormat, Reporter};
+
+fn finding(check_id: &str, severity: Severity, message: &str) -> Finding {
+    Finding {
+        check_id: check_id.into(),
+        group: "security".into(),
+        severity,
+        message: message.into(),
+        resource_kind: "Pod".into(),
+        resource_name: "web".into(),
+        resource_uid: Some("uid-1".into()),
+        namespace: Some("airflow".into()),
+        remediation: "fix it".into(),
+        profile: None,
+    }
+}
+
+#[test]
+fn test_format_from_str_accepts_known_formats_case_insensitively() {
+    assert_eq!("text".parse::<ReportFormat>().unwrap(), ReportFormat::Text);
+    assert_eq!("JSON".parse::<ReportFormat>().unwrap(), ReportFormat::Json);
+    assert_eq!("Sarif".parse::<ReportFormat>().unwrap(), ReportFormat::Sarif);
+}
+
+#[test]
+fn test_format_from_str_rejects_unknown_format() {
+    let err = "yaml".parse::<ReportFormat>().unwrap_err();
+    assert!(err.contains("yaml"));
+}
+
+#[test]
+fn test_reporter_text_writes_one_line_per_finding() {
+    let findings = vec![
+        finding("K01", Severity::Error, "hostPID is true"),
+        finding("K10", Severity::Warning, "uses image 'x:latest' with a mutable or missing tag"),
+    ];
+    let mut out = Vec::new();
+    Reporter::new(ReportFormat::Text).write(&findings, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], "[K01] hostPID is true");
+}
+
+#[test]
+fn test_reporter_json_round_trips_each_finding() {
+    let findings = vec![finding("K01", Severity::Error, "hostPID is true")];
+    let mut out = Vec::new();
+    Reporter::new(ReportFormat::Json).write(&findings, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 1);
+    let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!(value["check_id"], "K01");
+    assert_eq!(value["severity"], "error");
+    assert_eq!(value["message"], "hostPID is true");
+}
+
+#[test]
+fn test_reporter_sarif_maps_severity_to_level_and_populates_rules() {
+    let findings = vec![
+        finding("K01", Severity::Error, "hostPID is true"),
+        finding("K10", Severity::Warning, "untrusted registry"),
+    ];
+    let mut out = Vec::new();
+    Reporter::new(ReportFormat::Sarif).write(&findings, &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+
+    assert_eq!(value["version"], "2.1.0");
+    let rules = value["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+    assert!(rules.iter().any(|r| r["id"] == "K01"));
+    assert!(rules.iter().any(|r| r["id"] == "K10"));
+
+    let results = value["runs"][0]["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["ruleId"], "K01");
+    assert_eq!(results[0]["level"], "error");
+    assert_eq!(results[1]["level"], "warning");
+    assert_eq!(results[0]["locations"][0]["logicalLocations"][0]["fullyQualifiedName"], "airflow/Pod/web");
+}
+
+#[test]
+fn test_reporter_sarif_empty_findings_still_lists_rules() {
+    let mut out = Vec::new();
+    Reporter::new(ReportFormat::Sarif).write(&[], &mut out).unwrap();
+    let text = String::from_utf8(out).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let rules = value["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap();
+    assert!(!rules.is_empty());
+    assert!(value["runs"][0]["results"].as_array().unwrap().is_empty());
+}